@@ -2,26 +2,92 @@
 
 #![warn(clippy::all)]
 pub mod verify;
+pub mod prove;
 
 use std::io::Read;
+use std::io::Write;
 use std::num::NonZeroU8;
 
-// Type definition for a 9x9 array that will represent a Sudoku puzzle.
-// Entries with None represent unfilled positions in the puzzle.
-type Sudoku = [[Option<NonZeroU8>; 9]; 9];
+// A Sudoku board of arbitrary box-order `order` (N). The board side length is
+// `order * order` (N²), so order 3 is the familiar 9×9 puzzle, order 4 is
+// 16×16, order 5 is 25×25, and so on. Cells are stored in row-major order;
+// entries with None represent unfilled positions in the puzzle.
+pub struct Board {
+    pub order: usize,
+    pub cells: Vec<Option<NonZeroU8>>,
+}
+
+impl Board {
+    // Allocate an empty board of the given box-order.
+    pub fn new(order: usize) -> Self {
+        let side = order * order;
+        return Self {
+            order,
+            cells: vec![None; side * side],
+        };
+    }
+
+    // The side length of the board, N² for box-order N.
+    pub fn side(&self) -> usize {
+        return self.order * self.order;
+    }
+
+    // Read the cell at (r, c).
+    pub fn get(&self, r: usize, c: usize) -> Option<NonZeroU8> {
+        return self.cells[r * self.side() + c];
+    }
+
+    // Write the cell at (r, c).
+    pub fn set(&mut self, r: usize, c: usize, val: Option<NonZeroU8>) {
+        let side = self.side();
+        self.cells[r * side + c] = val;
+    }
+}
+
+// Alias kept so existing call sites (and the verifier module) keep reading as
+// "a Sudoku" even though the board is now order-generic.
+type Sudoku = Board;
+
+// Encode a cell value (1..=N²) as a single character: 1-9 become '1'..'9', then
+// 10, 11, … become 'A', 'B', … so puzzles above order 3 stay printable.
+fn encode_value(v: u8) -> char {
+    if v <= 9 {
+        return (v + b'0') as char;
+    }
+    return (v - 10 + b'A') as char;
+}
 
-// This function is called by main. It calls solve() to recursively find the solution.
-// The puzzle is modified in-place.
+// Decode a single puzzle character into a cell value, inverting `encode_value`.
+// Letters (`A`/`a` = 10, …) are only meaningful once the board side exceeds 9,
+// so a value outside `1..=side` is rejected — this keeps the order-3 path on
+// plain digits and guards against out-of-range cells.
+fn decode_value(b: u8, side: usize) -> Option<u8> {
+    let v = match b {
+        b'1'..=b'9' => b - b'0',
+        b'A'..=b'Z' => b - b'A' + 10,
+        b'a'..=b'z' => b - b'a' + 10,
+        _ => return None,
+    };
+    if v as usize <= side {
+        return Some(v);
+    }
+    return None;
+}
+
+// This function is called by main. It seeds the occupancy tables from the
+// clues, then hands off to the logic-driven search. The puzzle is modified
+// in-place.
 pub fn solve_puzzle(board: &mut Sudoku) {
-    let mut row_vals = [[false; 9]; 9];
-    let mut col_vals = [[false; 9]; 9];
-    let mut grid_vals = [[false; 9]; 9];
+    let side = board.side();
+    let mut row_vals = vec![vec![false; side]; side];
+    let mut col_vals = vec![vec![false; side]; side];
+    let mut grid_vals = vec![vec![false; side]; side];
 
-    for r in 0usize..=8 {
-        for c in 0usize..=8 {
-            match board[r][c] {
+    for r in 0..side {
+        for c in 0..side {
+            match board.get(r, c) {
                 Some(d) => {
-                    let g = grid_num(r, c);
+                    let g = grid_num(r, c, board.order);
                     let i = (d.get() - 1) as usize;
                     if row_vals[r][i] || col_vals[c][i] || grid_vals[g][i] {
                         panic!("Invalid initial board!");
@@ -36,95 +102,255 @@ pub fn solve_puzzle(board: &mut Sudoku) {
     }
 
     // Expect true, although we don't check
-    solve_sudoku_from(board, 0, 0, &mut row_vals, &mut col_vals, &mut grid_vals);
-}
-
-fn grid_num(r: usize, c: usize) -> usize {
-    // We could compute the grid num each time it's needed, but it's faster to make the grid nums
-    // statically allocated - there are only 9x9=81 of them anyways.
-    //
-    // If we wanted to compute the grid nums, the formula is:
-    // let mut g = r / 3;
-    // g *= 3;
-    // g += c / 3;
-    // return g;
-    static GRID_NUMS: [[usize; 9]; 9] = [
-        [0, 0, 0, 1, 1, 1, 2, 2, 2],
-        [0, 0, 0, 1, 1, 1, 2, 2, 2],
-        [0, 0, 0, 1, 1, 1, 2, 2, 2],
-        [3, 3, 3, 4, 4, 4, 5, 5, 5],
-        [3, 3, 3, 4, 4, 4, 5, 5, 5],
-        [3, 3, 3, 4, 4, 4, 5, 5, 5],
-        [6, 6, 6, 7, 7, 7, 8, 8, 8],
-        [6, 6, 6, 7, 7, 7, 8, 8, 8],
-        [6, 6, 6, 7, 7, 7, 8, 8, 8],
-    ];
-    return GRID_NUMS[r][c];
-}
-
-fn solve_sudoku_from(board: &mut Sudoku, r: usize, c: usize, row_vals: &mut [[bool; 9]; 9],
-                     col_vals: &mut [[bool; 9]; 9], grid_vals: &mut [[bool; 9]; 9]) -> bool {
-    match board[r][c] {
-        Some(_d) => {
-            if c + 1 < board[0].len() {
-                return solve_sudoku_from(board, r, c + 1, row_vals, col_vals, grid_vals);
-            } else if r + 1 < board.len() {
-                return solve_sudoku_from(board, r + 1, 0, row_vals, col_vals, grid_vals);
+    solve_logic_search(board, &mut row_vals, &mut col_vals, &mut grid_vals);
+}
+
+// Record a value (0-based index `i`) into a cell and the three occupancy tables
+// that cover it.
+fn place(board: &mut Sudoku, r: usize, c: usize, i: usize, row_vals: &mut [Vec<bool>],
+         col_vals: &mut [Vec<bool>], grid_vals: &mut [Vec<bool>]) {
+    let g = grid_num(r, c, board.order);
+    board.set(r, c, NonZeroU8::new((i + 1) as u8));
+    row_vals[r][i] = true;
+    col_vals[c][i] = true;
+    grid_vals[g][i] = true;
+}
+
+// Undo a value previously recorded by `place`.
+fn unplace(board: &mut Sudoku, r: usize, c: usize, i: usize, row_vals: &mut [Vec<bool>],
+           col_vals: &mut [Vec<bool>], grid_vals: &mut [Vec<bool>]) {
+    let g = grid_num(r, c, board.order);
+    board.set(r, c, None);
+    row_vals[r][i] = false;
+    col_vals[c][i] = false;
+    grid_vals[g][i] = false;
+}
+
+// Constraint-propagation pass ("AnySolver"-style). Repeatedly applies two rules
+// to a fixpoint: naked singles (a cell with a single remaining candidate) and
+// hidden singles (a value with a single remaining home in some unit). Every
+// assignment it makes is appended to `log` so the caller can undo the whole
+// pass on backtrack. Returns false if it reaches a contradiction (a cell with
+// no candidates, or a value with nowhere to go), true otherwise.
+//
+// Candidates are derived on demand by rescanning the row/column/box occupancy
+// tables rather than kept in a per-cell `u16` bitmask as originally sketched:
+// order 5 has 25 candidates per cell, which a `u16` cannot hold, so the
+// occupancy tables (already the repo's representation) are reused at the cost
+// of an O(side³) sweep per fixpoint iteration.
+fn solve_logic(board: &mut Sudoku, row_vals: &mut [Vec<bool>], col_vals: &mut [Vec<bool>],
+               grid_vals: &mut [Vec<bool>], log: &mut Vec<(usize, usize, usize)>) -> bool {
+    let side = board.side();
+    let order = board.order;
+    loop {
+        let mut changed = false;
+
+        // Naked single: an empty cell with exactly one candidate is forced.
+        for r in 0..side {
+            for c in 0..side {
+                if board.get(r, c).is_some() {
+                    continue;
+                }
+                let g = grid_num(r, c, order);
+                let mut count = 0;
+                let mut only = 0;
+                for i in 0..side {
+                    if !row_vals[r][i] && !col_vals[c][i] && !grid_vals[g][i] {
+                        count += 1;
+                        only = i;
+                    }
+                }
+                if count == 0 {
+                    return false;
+                }
+                if count == 1 {
+                    place(board, r, c, only, row_vals, col_vals, grid_vals);
+                    log.push((r, c, only));
+                    changed = true;
+                }
             }
-            return true;
-        },
-        None => {
-            let g = grid_num(r, c);
-            for i in 0usize..=8 {
-                if row_vals[r][i] || col_vals[c][i] || grid_vals[g][i] {
+        }
+
+        // Hidden single: a value that can go in only one cell of a unit is
+        // forced there. We scan each unit kind in turn.
+        for i in 0..side {
+            // Rows.
+            for r in 0..side {
+                if row_vals[r][i] {
+                    continue;
+                }
+                match hidden_single_pos(board, i, row_vals, col_vals, grid_vals, |k| (r, k)) {
+                    Some(Some(c)) => {
+                        place(board, r, c, i, row_vals, col_vals, grid_vals);
+                        log.push((r, c, i));
+                        changed = true;
+                    }
+                    Some(None) => return false,
+                    None => {}
+                }
+            }
+            // Columns.
+            for c in 0..side {
+                if col_vals[c][i] {
                     continue;
                 }
-                board[r][c] = NonZeroU8::new((i + 1) as u8);
-                row_vals[r][i] = true;
-                col_vals[c][i] = true;
-                grid_vals[g][i] = true;
-                if c + 1 < board[0].len() {
-                    if solve_sudoku_from(board, r, c + 1, row_vals, col_vals, grid_vals) {
-                        return true;
+                match hidden_single_pos(board, i, row_vals, col_vals, grid_vals, |k| (k, c)) {
+                    Some(Some(r)) => {
+                        place(board, r, c, i, row_vals, col_vals, grid_vals);
+                        log.push((r, c, i));
+                        changed = true;
                     }
-                } else if r + 1 < board.len() {
-                    if solve_sudoku_from(board, r + 1, 0, row_vals, col_vals, grid_vals) {
-                        return true;
+                    Some(None) => return false,
+                    None => {}
+                }
+            }
+            // Boxes.
+            for g in 0..side {
+                if grid_vals[g][i] {
+                    continue;
+                }
+                let base_r = (g / order) * order;
+                let base_c = (g % order) * order;
+                match hidden_single_pos(board, i, row_vals, col_vals, grid_vals,
+                                        |k| (base_r + k / order, base_c + k % order)) {
+                    Some(Some(k)) => {
+                        let (r, c) = (base_r + k / order, base_c + k % order);
+                        place(board, r, c, i, row_vals, col_vals, grid_vals);
+                        log.push((r, c, i));
+                        changed = true;
                     }
-                } else {
+                    Some(None) => return false,
+                    None => {}
+                }
+            }
+        }
+
+        if !changed {
+            break;
+        }
+    }
+    return true;
+}
+
+// Scan the `side` cells of one unit, addressed by `cell(k)`, looking for the
+// homes of candidate value `i`. Returns None if the value already has more than
+// one possible home (nothing to deduce), `Some(Some(pos))` if it has exactly
+// one, and `Some(None)` if it has none (a contradiction for the caller).
+fn hidden_single_pos(board: &Sudoku, i: usize, row_vals: &[Vec<bool>], col_vals: &[Vec<bool>],
+                     grid_vals: &[Vec<bool>], cell: impl Fn(usize) -> (usize, usize))
+                     -> Option<Option<usize>> {
+    let side = board.side();
+    let order = board.order;
+    let mut count = 0;
+    let mut pos = 0;
+    for k in 0..side {
+        let (r, c) = cell(k);
+        if board.get(r, c).is_some() {
+            continue;
+        }
+        let g = grid_num(r, c, order);
+        if !row_vals[r][i] && !col_vals[c][i] && !grid_vals[g][i] {
+            count += 1;
+            pos = k;
+        }
+    }
+    match count {
+        0 => Some(None),
+        1 => Some(Some(pos)),
+        _ => None,
+    }
+}
+
+// Logic-first search. Propagates to a fixpoint, then branches on the cell with
+// the fewest candidates (Minimum-Remaining-Values) to keep the branching factor
+// down. Returns true if a solution was found, leaving it in `board`.
+fn solve_logic_search(board: &mut Sudoku, row_vals: &mut [Vec<bool>],
+                      col_vals: &mut [Vec<bool>], grid_vals: &mut [Vec<bool>]) -> bool {
+    let side = board.side();
+    let order = board.order;
+
+    let mut log = Vec::new();
+    if !solve_logic(board, row_vals, col_vals, grid_vals, &mut log) {
+        undo(board, row_vals, col_vals, grid_vals, &log);
+        return false;
+    }
+
+    // Pick the unassigned cell with the fewest remaining candidates.
+    let mut best: Option<(usize, usize, usize)> = None;
+    for r in 0..side {
+        for c in 0..side {
+            if board.get(r, c).is_some() {
+                continue;
+            }
+            let g = grid_num(r, c, order);
+            let count = (0..side)
+                .filter(|&i| !row_vals[r][i] && !col_vals[c][i] && !grid_vals[g][i])
+                .count();
+            if best.is_none_or(|(_, _, bc)| count < bc) {
+                best = Some((r, c, count));
+            }
+        }
+    }
+
+    match best {
+        None => return true,  // every cell is filled: solved
+        Some((r, c, _)) => {
+            let g = grid_num(r, c, order);
+            for i in 0..side {
+                if row_vals[r][i] || col_vals[c][i] || grid_vals[g][i] {
+                    continue;
+                }
+                place(board, r, c, i, row_vals, col_vals, grid_vals);
+                if solve_logic_search(board, row_vals, col_vals, grid_vals) {
                     return true;
                 }
-                board[r][c] = None;
-                row_vals[r][i] = false;
-                col_vals[c][i] = false;
-                grid_vals[g][i] = false;
+                unplace(board, r, c, i, row_vals, col_vals, grid_vals);
             }
+            undo(board, row_vals, col_vals, grid_vals, &log);
             return false;
         }
     }
 }
 
+// Undo a batch of placements (most recent first) recorded by `solve_logic`.
+fn undo(board: &mut Sudoku, row_vals: &mut [Vec<bool>], col_vals: &mut [Vec<bool>],
+        grid_vals: &mut [Vec<bool>], log: &[(usize, usize, usize)]) {
+    for &(r, c, i) in log.iter().rev() {
+        unplace(board, r, c, i, row_vals, col_vals, grid_vals);
+    }
+}
+
+fn grid_num(r: usize, c: usize, order: usize) -> usize {
+    // The box index for a cell, laid out left-to-right then top-to-bottom:
+    // (r / N) * N + c / N, where N is the box-order.
+    return (r / order) * order + c / order;
+}
+
 // Helper for printing a sudoku puzzle to stdout for debugging.
 pub fn print_puzzle(puzzle: &Sudoku) {
-    for row in puzzle.iter() {
-        for elem in row.iter() {
-            print!("{}", elem.map(|e| (e.get() + b'0') as char).unwrap_or('.'));
+    let side = puzzle.side();
+    for r in 0..side {
+        for c in 0..side {
+            let ch = puzzle.get(r, c).map(|e| encode_value(e.get())).unwrap_or('.');
+            print!("{}", ch);
         }
         print!("\n");
     }
     print!("\n");
 }
 
-// Read the input byte by byte until a complete Sudoku puzzle has been
-// read or EOF is reached.  Assumes the input follows the correct format
-// (i.e. matching the files in the input folder).
-pub fn read_puzzle(reader: &mut impl Read) -> Option<Box<Sudoku>> {
+// Read the input byte by byte until a complete Sudoku puzzle of the given
+// box-order has been read or EOF is reached.  Assumes the input follows the
+// correct format (i.e. matching the files in the input folder), with values
+// above 9 encoded as letters (see `decode_value`).
+pub fn read_puzzle(reader: &mut impl Read, order: usize) -> Option<Box<Sudoku>> {
+    let side = order * order;
     // Turn the input stream into an iterator of bytes
     let mut bytes = reader.bytes().map(|b| b.expect("input error")).peekable();
     // Go thru the input until we find a puzzle or EOF (None)
     loop {
         match bytes.peek() {
-            Some(b'1'..=b'9') | Some(b'.') => break,
+            Some(&b) if b == b'.' || decode_value(b, side).is_some() => break,
             None => return None,
             _ => {
                 bytes.next();
@@ -132,35 +358,137 @@ pub fn read_puzzle(reader: &mut impl Read) -> Option<Box<Sudoku>> {
         }
     }
 
-    let mut puzzle = Box::new([[None; 9]; 9]);
+    let mut puzzle = Box::new(Board::new(order));
     // Fill in the puzzle matrix. Ignore the non-puzzle input bytes.
-    for i in 0..9 {
+    for i in 0..side {
         let mut j = 0;
-        while j < 9 {
+        while j < side {
             let b = bytes.next().expect("unexpected EOF");
 
             let elem = match b {
-                b'1'..=b'9' => NonZeroU8::new(b - b'0'),
                 b'.' => None,
-                _ => continue,
+                _ => match decode_value(b, side) {
+                    Some(v) => NonZeroU8::new(v),
+                    None => continue,
+                },
             };
-            puzzle[i][j] = elem;
+            puzzle.set(i, j, elem);
             j += 1;
         }
     }
     return Some(puzzle);
 }
 
+// Parse the ksudoku puzzle/solution record used by sibling Rust sudoku
+// projects. The record is a small JSON object carrying a `puzzle` string, an
+// optional `solution` string, a `graph`/type tag ("Plain"/"sudoku"), and an
+// `order` which is the board side length (9/16/25). In the strings `_` is a
+// blank and the offset from `'a'` encodes the value (`'b'` = 1, `'c'` = 2, …).
+// Returns the puzzle and, when present, the embedded solution so callers like
+// `check_puzzle` can validate against the known answer.
+pub fn read_ksudoku(reader: &mut impl Read) -> Option<(Box<Sudoku>, Option<Box<Sudoku>>)> {
+    let mut text = String::new();
+    reader.read_to_string(&mut text).ok()?;
+
+    // The ksudoku `order` is the side length; our box-order is its square root.
+    let order = isqrt(json_number(&text, "order")? as usize);
+    let puzzle = decode_ksudoku(&json_string(&text, "puzzle")?, order)?;
+    let solution = match json_string(&text, "solution") {
+        Some(s) if !s.is_empty() => decode_ksudoku(&s, order),
+        _ => None,
+    };
+    return Some((puzzle, solution));
+}
+
+// Re-encode a (typically solved) board as a ksudoku record, inverting
+// `read_ksudoku`. Both the `puzzle` and `solution` strings are emitted (the
+// board doubles as its own solution) so the output round-trips back through
+// `read_ksudoku`.
+pub fn write_ksudoku(puzzle: &Sudoku, writer: &mut impl Write) -> std::io::Result<()> {
+    write!(writer, "{{\"order\": {}, \"graph\": \"Plain\", \"puzzle\": \"", puzzle.side())?;
+    write_ksudoku_cells(puzzle, writer)?;
+    write!(writer, "\", \"solution\": \"")?;
+    write_ksudoku_cells(puzzle, writer)?;
+    write!(writer, "\"}}")?;
+    return Ok(());
+}
+
+// Emit the `_`/`'a'`-offset value string for a board.
+fn write_ksudoku_cells(puzzle: &Sudoku, writer: &mut impl Write) -> std::io::Result<()> {
+    for &cell in puzzle.cells.iter() {
+        let ch = match cell {
+            None => '_',
+            Some(v) => (b'a' + v.get()) as char,
+        };
+        write!(writer, "{}", ch)?;
+    }
+    return Ok(());
+}
+
+// Decode a ksudoku value string into a board of the given box-order.
+fn decode_ksudoku(s: &str, order: usize) -> Option<Box<Sudoku>> {
+    let side = order * order;
+    if s.len() != side * side {
+        return None;
+    }
+    let mut board = Box::new(Board::new(order));
+    for (idx, b) in s.bytes().enumerate() {
+        board.cells[idx] = match b {
+            b'_' => None,
+            // `'b'` = 1, …; `'a'` (value 0) and anything above `side` are
+            // malformed, so reject the whole record like the length check does.
+            b'b'..=b'z' if (b - b'a') as usize <= side => NonZeroU8::new(b - b'a'),
+            _ => return None,
+        };
+    }
+    return Some(board);
+}
+
+// Extract the first string value for `key` from a flat JSON object by hand,
+// matching the hand-rolled JSON style used in `verify`.
+fn json_string(text: &str, key: &str) -> Option<String> {
+    let pat = format!("\"{}\"", key);
+    let rest = &text[text.find(&pat)? + pat.len()..];
+    let after = &rest[rest.find(':')? + 1..];
+    let open = after.find('"')? + 1;
+    let close = after[open..].find('"')?;
+    return Some(after[open..open + close].to_string());
+}
+
+// Extract the first non-negative integer value for `key` from a flat JSON object.
+fn json_number(text: &str, key: &str) -> Option<u64> {
+    let pat = format!("\"{}\"", key);
+    let rest = &text[text.find(&pat)? + pat.len()..];
+    let after = rest[rest.find(':')? + 1..].trim_start();
+    let end = after.find(|ch: char| !ch.is_ascii_digit()).unwrap_or(after.len());
+    if end == 0 {
+        return None;
+    }
+    return after[..end].parse().ok();
+}
+
+// Integer square root, used to turn a ksudoku side length into a box-order.
+fn isqrt(n: usize) -> usize {
+    let mut r = 0;
+    while (r + 1) * (r + 1) <= n {
+        r += 1;
+    }
+    return r;
+}
+
 // Do a simple check that the puzzle is valid.
 // Returns true if it is valid, false if it is not.
 // (The verifier server doesn't tell you what's wrong so this function can also help you track
 // down an error if your puzzles are not being solved correctly.)
 pub fn check_puzzle(puzzle: &Sudoku) -> bool {
+    let side = puzzle.side();
+    let order = puzzle.order;
+
     // Check that each row is valid
-    for r in 0..9 {
-        let mut row_vals = [false; 9];
-        for c in 0..9 {
-            match puzzle[r][c] {
+    for r in 0..side {
+        let mut row_vals = vec![false; side];
+        for c in 0..side {
+            match puzzle.get(r, c) {
                 None => return false,
                 Some(val) => {
                     let val = val.get() as usize;
@@ -174,10 +502,10 @@ pub fn check_puzzle(puzzle: &Sudoku) -> bool {
     }
 
     // Check that each column is valid
-    for c in 0..9 {
-        let mut col_vals = [false; 9];
-        for r in 0..9 {
-            match puzzle[r][c] {
+    for c in 0..side {
+        let mut col_vals = vec![false; side];
+        for r in 0..side {
+            match puzzle.get(r, c) {
                 None => return false,
                 Some(val) => {
                     let val = val.get() as usize;
@@ -190,15 +518,15 @@ pub fn check_puzzle(puzzle: &Sudoku) -> bool {
         }
     }
 
-    // Check that each 3x3 box is valid
-    for i in (0..7).step_by(3) {
-        for j in (0..7).step_by(3) {
-            let mut grid_vals = [false; 9];
+    // Check that each NxN box is valid
+    for i in (0..side).step_by(order) {
+        for j in (0..side).step_by(order) {
+            let mut grid_vals = vec![false; side];
             let mut r = i;
-            while r < i + 3 {
+            while r < i + order {
                 let mut c = j;
-                while c < j + 3 {
-                    match puzzle[r][c] {
+                while c < j + order {
+                    match puzzle.get(r, c) {
                         None => return false,
                         Some(val) => {
                             let val = val.get() as usize;
@@ -217,3 +545,63 @@ pub fn check_puzzle(puzzle: &Sudoku) -> bool {
 
     return true;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn digit_value_roundtrips() {
+        // 1..=9 round-trip through the plain-digit encoding on an order-3 board.
+        for v in 1..=9u8 {
+            assert_eq!(decode_value(encode_value(v) as u8, 9), Some(v));
+        }
+        // Letters only carry values once the side exceeds 9.
+        assert_eq!(decode_value(b'A', 9), None);
+        assert_eq!(decode_value(b'A', 16), Some(10));
+        assert_eq!(encode_value(10), 'A');
+        // A value beyond the side is rejected, as is a non-digit.
+        assert_eq!(decode_value(b'Z', 16), None);
+        assert_eq!(decode_value(b'.', 9), None);
+    }
+
+    // A classic order-3 puzzle used across the solver/ksudoku tests.
+    const SAMPLE: &[u8] = b"53..7....\n6..195...\n.98....6.\n8...6...3\n\
+                            4..8.3..1\n7...2...6\n.6....28.\n...419..5\n....8..79\n";
+
+    #[test]
+    fn solves_a_known_puzzle() {
+        let mut board = *read_puzzle(&mut &SAMPLE[..], 3).unwrap();
+        solve_puzzle(&mut board);
+        assert!(check_puzzle(&board));
+    }
+
+    #[test]
+    fn isqrt_of_side_lengths() {
+        assert_eq!(isqrt(9), 3);
+        assert_eq!(isqrt(16), 4);
+        assert_eq!(isqrt(25), 5);
+    }
+
+    #[test]
+    fn ksudoku_roundtrips_a_solved_board() {
+        let mut board = *read_puzzle(&mut &SAMPLE[..], 3).unwrap();
+        solve_puzzle(&mut board);
+
+        let mut buf = Vec::new();
+        write_ksudoku(&board, &mut buf).unwrap();
+        let (puzzle, solution) = read_ksudoku(&mut &buf[..]).unwrap();
+
+        assert_eq!(puzzle.cells, board.cells);
+        assert_eq!(solution.unwrap().cells, board.cells);
+    }
+
+    #[test]
+    fn ksudoku_rejects_out_of_range_values() {
+        // Order-3 side is 9, so 'z' (value 25) and 'a' (value 0) are malformed.
+        let bad = format!("{{\"order\": 9, \"puzzle\": \"{}\"}}", "z".repeat(81));
+        assert!(read_ksudoku(&mut bad.as_bytes()).is_none());
+        let blank = format!("{{\"order\": 9, \"puzzle\": \"{}\"}}", "a".repeat(81));
+        assert!(read_ksudoku(&mut blank.as_bytes()).is_none());
+    }
+}
@@ -0,0 +1,187 @@
+// Zero-knowledge-style completion proof for a solved puzzle.
+//
+// This implements a cut-and-choose commitment protocol, in the spirit of
+// pay-to-sudoku's "prove you solved it without revealing the solution". The
+// prover commits to every cell as `H(value || nonce)` (SHA-256). The verifier
+// then repeatedly challenges a random unit (a row, column, or box); the prover
+// opens just those cells, and the verifier checks that each opening matches its
+// commitment, that the revealed values form a permutation of 1..=N², and that
+// any cell fixed by the original clue opens to the clue's value. Each round a
+// cheating prover survives with probability at most 1 - 1/(3N²), so running
+// many independent rounds drives that down exponentially while the unopened
+// cells stay hidden.
+
+use crate::Sudoku;
+use sha2::{Digest, Sha256};
+
+// A single cell commitment: the SHA-256 digest of `value || nonce`.
+pub type Commitment = [u8; 32];
+
+// One of the 3·N² units the verifier can challenge.
+pub enum Unit {
+    Row(usize),
+    Col(usize),
+    Box(usize),
+}
+
+// The public commitments, one per cell in row-major order.
+pub struct Commitments {
+    pub order: usize,
+    pub cells: Vec<Commitment>,
+}
+
+// The prover's private table of values and nonces, kept so any challenged unit
+// can be opened later.
+pub struct Openings {
+    pub order: usize,
+    pub values: Vec<u8>,
+    pub nonces: Vec<[u8; 16]>,
+}
+
+// The prover's response to one challenge: the (value, nonce) of each cell in
+// the challenged unit, in unit order.
+pub struct Opening {
+    pub cells: Vec<(u8, [u8; 16])>,
+}
+
+// Commit to a completed board: draw a fresh random nonce per cell and publish
+// `H(value || nonce)`. Returns the public commitments and the private openings.
+pub fn commit_solution(board: &Sudoku) -> (Commitments, Openings) {
+    let side = board.side();
+    let mut cells = Vec::with_capacity(side * side);
+    let mut values = Vec::with_capacity(side * side);
+    let mut nonces = Vec::with_capacity(side * side);
+
+    for r in 0..side {
+        for c in 0..side {
+            let value = board.get(r, c).map(|v| v.get()).unwrap_or(0);
+            let nonce: [u8; 16] = rand::random();
+            cells.push(commit_cell(value, &nonce));
+            values.push(value);
+            nonces.push(nonce);
+        }
+    }
+
+    return (
+        Commitments { order: board.order, cells },
+        Openings { order: board.order, values, nonces },
+    );
+}
+
+// Derive the unit a verifier challenges from a random seed, uniformly over the
+// 3·N² rows, columns, and boxes. This takes the board `order` in addition to
+// the `seed` from the protocol sketch, since a bare seed can't know how many
+// units the board has.
+pub fn challenge(seed: u64, order: usize) -> Unit {
+    let side = order * order;
+    // splitmix64, so a given seed always names the same unit.
+    let mut z = seed.wrapping_add(0x9E3779B97F4A7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+    z ^= z >> 31;
+
+    let index = (z as usize) % side;
+    match (z >> 32) % 3 {
+        0 => Unit::Row(index),
+        1 => Unit::Col(index),
+        _ => Unit::Box(index),
+    }
+}
+
+// Open the cells of the challenged unit from the private openings.
+pub fn open(openings: &Openings, unit: &Unit) -> Opening {
+    let side = openings.order * openings.order;
+    let cells = unit_cells(unit, openings.order)
+        .into_iter()
+        .map(|(r, c)| (openings.values[r * side + c], openings.nonces[r * side + c]))
+        .collect();
+    return Opening { cells };
+}
+
+// Verify one challenge round. Checks that every opening hashes to its published
+// commitment, that the revealed values are a permutation of 1..=N² (the same
+// permutation property `check_puzzle` enforces per unit), and that any clue
+// fixed by the original puzzle opens to the clue's value.
+pub fn verify_round(puzzle: &Sudoku, commitments: &Commitments, opening: &Opening,
+                    unit: &Unit) -> bool {
+    let order = commitments.order;
+    let side = order * order;
+    let positions = unit_cells(unit, order);
+    if opening.cells.len() != side {
+        return false;
+    }
+
+    let mut seen = vec![false; side];
+    for (k, (r, c)) in positions.into_iter().enumerate() {
+        let (value, nonce) = opening.cells[k];
+
+        // The opening must reproduce the published commitment.
+        if commit_cell(value, &nonce) != commitments.cells[r * side + c] {
+            return false;
+        }
+
+        // The opened values must be a permutation of 1..=N².
+        if value < 1 || value as usize > side || seen[value as usize - 1] {
+            return false;
+        }
+        seen[value as usize - 1] = true;
+
+        // A cell fixed by the original clue must open to that clue.
+        if let Some(clue) = puzzle.get(r, c) {
+            if clue.get() != value {
+                return false;
+            }
+        }
+    }
+
+    return true;
+}
+
+// The SHA-256 commitment to a single cell: H(value || nonce).
+fn commit_cell(value: u8, nonce: &[u8; 16]) -> Commitment {
+    let mut hasher = Sha256::new();
+    hasher.update([value]);
+    hasher.update(nonce);
+    return hasher.finalize().into();
+}
+
+// The (row, column) coordinates of the cells making up a unit, in unit order.
+fn unit_cells(unit: &Unit, order: usize) -> Vec<(usize, usize)> {
+    let side = order * order;
+    match *unit {
+        Unit::Row(r) => (0..side).map(|c| (r, c)).collect(),
+        Unit::Col(c) => (0..side).map(|r| (r, c)).collect(),
+        Unit::Box(b) => {
+            let base_r = (b / order) * order;
+            let base_c = (b % order) * order;
+            (0..side).map(|k| (base_r + k / order, base_c + k % order)).collect()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{read_puzzle, solve_puzzle};
+
+    const SAMPLE: &[u8] = b"53..7....\n6..195...\n.98....6.\n8...6...3\n\
+                            4..8.3..1\n7...2...6\n.6....28.\n...419..5\n....8..79\n";
+
+    #[test]
+    fn honest_opening_verifies_tampered_one_does_not() {
+        let puzzle = *read_puzzle(&mut &SAMPLE[..], 3).unwrap();
+        let mut solved = *read_puzzle(&mut &SAMPLE[..], 3).unwrap();
+        solve_puzzle(&mut solved);
+
+        let (commitments, openings) = commit_solution(&solved);
+        let unit = challenge(42, solved.order);
+
+        let opening = open(&openings, &unit);
+        assert!(verify_round(&puzzle, &commitments, &opening, &unit));
+
+        // Tampering with a revealed value breaks the opening.
+        let mut tampered = open(&openings, &unit);
+        tampered.cells[0].0 ^= 0xFF;
+        assert!(!verify_round(&puzzle, &commitments, &tampered, &unit));
+    }
+}
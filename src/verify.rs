@@ -5,9 +5,9 @@ use curl::easy;
 use curl::multi;
 use std::io::Write;
 use curl::multi::Easy2Handle;
+use sha2::{Digest, Sha256};
 
 const URL: &str = "54.209.48.141:4590/verify";  // the verification server
-const MATRIX_LENGTH: usize = 202;
 
 // Callback handler for the curl easy handles
 struct SudokuHandler {
@@ -59,7 +59,11 @@ fn create_easy(puzzle: Box<Sudoku>) -> Result<easy::Easy2<SudokuHandler>, curl::
     easy.http_headers(headers)?;
     easy.url(URL)?;
     easy.post(true)?;  // we use HTTP "POST" instead of "GET"
-    easy.post_field_size(MATRIX_LENGTH as u64)?;
+    // The JSON length depends on the board order, so measure the serialized
+    // form instead of assuming a fixed 9x9 matrix.
+    let mut buf = Vec::new();
+    write_puzzle_to_json(&easy.get_ref().puzzle, &mut buf).expect("JSON writing error");
+    easy.post_field_size(buf.len() as u64)?;
     return Ok(easy);  // result is a curl easy handle
 }
 
@@ -72,21 +76,22 @@ fn create_multi(max_total_connections: usize) -> Result<multi::Multi, curl::Mult
 
 // convert a puzzle into JSON format to send to the server
 fn write_puzzle_to_json(puzzle: &Sudoku, writer: &mut impl Write) -> std::io::Result<()> {
+    let side = puzzle.side();
     write!(writer, "{{\"content\": [")?;
 
-    for (i, row) in puzzle.iter().enumerate() {
+    for r in 0..side {
         write!(writer, "[")?;
 
-        for (j, elem) in row.iter().enumerate() {
-            let val = elem.map(|e| e.get()).unwrap_or(0);
+        for c in 0..side {
+            let val = puzzle.get(r, c).map(|e| e.get()).unwrap_or(0);
             write!(writer, "{}", val)?;
-            if j < 8 {
+            if c + 1 < side {
                 write!(writer, ",")?;
             }
         }
 
         write!(writer, "]")?;
-        if i < 8 {
+        if r + 1 < side {
             write!(writer, ", ")?;
         }
     }
@@ -95,42 +100,206 @@ fn write_puzzle_to_json(puzzle: &Sudoku, writer: &mut impl Write) -> std::io::Re
     return Ok(());
 }
 
-// This function is called from main to verify all of the puzzles
-pub fn verify_puzzles(puzzles: impl Iterator<Item = Box<Sudoku>>, max_total_connections: usize) {
-    verify_puzzles_multi_poll(puzzles, max_total_connections);
+// The default location of the persistent verification cache.
+const CACHE_PATH: &str = "verify_cache";
+
+// A small embedded store recording, per canonical puzzle fingerprint, whether
+// the server has already accepted it ("1"). Backed by sled, mirroring the
+// sled→Postgres persistence layer jigsaw uses, so results survive across runs.
+struct VerifyCache {
+    db: sled::Db,
+}
+
+impl VerifyCache {
+    fn open(path: &str) -> Option<Self> {
+        match sled::open(path) {
+            Ok(db) => Some(Self { db }),
+            Err(e) => {
+                println!("Cache disabled ({}): {}", path, e);
+                None
+            }
+        }
+    }
+
+    // Whether this fingerprint has already been accepted by the server. Only
+    // positive ("1") outcomes are recorded, so a rejection or transport error
+    // is never pinned across runs.
+    fn contains(&self, fingerprint: &[u8]) -> bool {
+        return matches!(self.db.get(fingerprint), Ok(Some(_)));
+    }
+
+    // Record that this fingerprint was accepted by the server.
+    fn record_verified(&self, fingerprint: &[u8]) {
+        let _ = self.db.insert(fingerprint, &b"1"[..]);
+    }
+}
+
+// The canonical fingerprint of a puzzle: the SHA-256 digest of its canonical
+// JSON form, so identical boards map to the same cache key regardless of how
+// they were read. A wide cryptographic digest is used (rather than a truncated
+// non-crypto hash) because a collision would mis-report an unverified puzzle as
+// verified.
+fn fingerprint(puzzle: &Sudoku) -> Vec<u8> {
+    let mut json = Vec::new();
+    write_puzzle_to_json(puzzle, &mut json).expect("JSON writing error");
+    let mut hasher = Sha256::new();
+    hasher.update(&json);
+    return hasher.finalize().to_vec();
+}
+
+// Base delay for the exponential backoff between transport-layer retries.
+const RETRY_BACKOFF: std::time::Duration = std::time::Duration::from_millis(200);
+
+// This function is called from main to verify all of the puzzles. `cache_path`
+// selects the persistent fingerprint cache location (defaulting to
+// `CACHE_PATH`), and `no_cache` bypasses it entirely; `max_retries` bounds how
+// many times a handle that failed at the transport layer is retried.
+pub fn verify_puzzles(puzzles: impl Iterator<Item = Box<Sudoku>>, max_total_connections: usize,
+                      cache_path: Option<&str>, no_cache: bool, max_retries: usize) {
+    let cache = if no_cache {
+        None
+    } else {
+        VerifyCache::open(cache_path.unwrap_or(CACHE_PATH))
+    };
+    verify_puzzles_multi_poll(puzzles, max_total_connections, cache.as_ref(), max_retries);
     // verify_puzzles_easy(puzzles, max_total_connections);
 }
 
-// Use a multi handle - poll the easy handles using curl_multi_wait and curl_multi_perform
-fn verify_puzzles_multi_poll(puzzles: impl Iterator<Item = Box<Sudoku>>, max_total_connections: usize) {
+// Use a multi handle - poll the easy handles using curl_multi_wait and curl_multi_perform.
+// Completion messages are drained after each perform() so we can tell a puzzle
+// the server rejected apart from a transfer that errored at the transport layer
+// (timeout, connection reset, …); the latter are retried with exponential
+// backoff and, if still failing, reported in their own bucket.
+fn verify_puzzles_multi_poll(puzzles: impl Iterator<Item = Box<Sudoku>>, max_total_connections: usize,
+                             cache: Option<&VerifyCache>, max_retries: usize) {
     let mut total = 0;
     let mut verified = 0;
+    let mut rejected = 0;
+    let mut transport_failed = 0;
 
-    let mut owned_easies: Vec<Easy2Handle<SudokuHandler>> = vec![];
+    // Per-token state, indexed by the token set with `set_token`. A slot holds
+    // the live handle while in flight and is taken out on completion.
+    let mut slots: Vec<Option<Easy2Handle<SudokuHandler>>> = vec![];
+    let mut fingerprints: Vec<Vec<u8>> = vec![];
+    let mut attempts: Vec<usize> = vec![];
+    // Handles waiting out their backoff, each with the instant it may re-enter
+    // the multi. Kept out of the multi so a backoff never stalls the other
+    // in-flight transfers.
+    let mut retry_queue: Vec<(usize, easy::Easy2<SudokuHandler>, std::time::Instant)> = vec![];
     let multi = create_multi(max_total_connections).unwrap();
 
-    // Add all easy handles to the multi
+    // Add all easy handles to the multi, skipping puzzles already in the cache.
     for puzzle in puzzles {
+        total += 1;
+        let fp = fingerprint(&puzzle);
+        if let Some(cache) = cache {
+            if cache.contains(&fp) {
+                verified += 1;
+                continue;
+            }
+        }
         let easy = create_easy(puzzle).unwrap();
+        let token = slots.len();
         let mut owned_easy = multi.add2(easy).unwrap();
-        owned_easy.set_token(owned_easies.len());
-        owned_easies.push(owned_easy);
-        total += 1;
+        owned_easy.set_token(token);
+        slots.push(Some(owned_easy));
+        fingerprints.push(fp);
+        attempts.push(0);
     }
 
-    // Wait until they're all done
-    while multi.perform().unwrap() > 0 {
-        multi.wait(&mut[], std::time::Duration::from_secs(30));
-    }
+    // Drive the transfers, draining completion messages each round.
+    loop {
+        // Re-queue any handles whose backoff has elapsed.
+        let now = std::time::Instant::now();
+        let mut i = 0;
+        while i < retry_queue.len() {
+            if retry_queue[i].2 <= now {
+                let (token, handle, _) = retry_queue.remove(i);
+                let mut requeued = multi.add2(handle).unwrap();
+                requeued.set_token(token);
+                slots[token] = Some(requeued);
+            } else {
+                i += 1;
+            }
+        }
 
-    for owned_easy in owned_easies.into_iter() {
-        let mut easy = multi.remove2(owned_easy).unwrap();
-        if easy.get_ref().result {
-            verified += 1;
+        let active = slots.iter().any(|s| s.is_some());
+        if !active && retry_queue.is_empty() {
+            break;
         }
+
+        if !active {
+            // Nothing in flight: wait out the soonest backoff, then loop.
+            if let Some(until) = retry_queue.iter().map(|e| e.2).min() {
+                std::thread::sleep(until.saturating_duration_since(std::time::Instant::now()));
+            }
+            continue;
+        }
+
+        multi.perform().unwrap();
+
+        // Collect completions first; we can't mutate the multi inside the
+        // messages() closure.
+        let mut completed: Vec<(usize, Result<(), curl::Error>)> = vec![];
+        multi.messages(|msg| {
+            if let Ok(token) = msg.token() {
+                if let Some(handle) = slots[token].as_ref() {
+                    if let Some(result) = msg.result_for2(handle) {
+                        completed.push((token, result));
+                    }
+                }
+            }
+        });
+
+        for (token, result) in completed {
+            let handle = slots[token].take().unwrap();
+            let easy = multi.remove2(handle).unwrap();
+            match result {
+                Ok(()) => {
+                    let result = easy.get_ref().result;
+                    if result { verified += 1; } else { rejected += 1; }
+                    // Only positive outcomes are cached (see `record_verified`).
+                    if result {
+                        if let Some(cache) = cache {
+                            cache.record_verified(&fingerprints[token]);
+                        }
+                    }
+                }
+                Err(e) => {
+                    let status = easy.response_code().unwrap_or(0);
+                    if attempts[token] < max_retries {
+                        // Schedule a retry after an exponential backoff without
+                        // blocking the loop; the shift is clamped so it can't
+                        // overflow once many retries have accumulated.
+                        let shift = attempts[token].min(10) as u32;
+                        let delay = RETRY_BACKOFF * (1u32 << shift);
+                        attempts[token] += 1;
+                        eprintln!("Transfer error on puzzle {} (curl {}, HTTP {}); retry {}/{}",
+                                  token, e.code(), status, attempts[token], max_retries);
+                        retry_queue.push((token, easy, std::time::Instant::now() + delay));
+                    } else {
+                        eprintln!("Transfer failed on puzzle {} (curl {}, HTTP {}); giving up",
+                                  token, e.code(), status);
+                        transport_failed += 1;
+                    }
+                }
+            }
+        }
+
+        // Wake in time for the soonest pending retry, but no longer than 30s.
+        let now = std::time::Instant::now();
+        let mut timeout = std::time::Duration::from_secs(30);
+        for &(_, _, at) in &retry_queue {
+            let d = at.saturating_duration_since(now);
+            if d < timeout {
+                timeout = d;
+            }
+        }
+        multi.wait(&mut[], timeout);
     }
 
-    println!("Verified {} out of {}", verified, total);
+    println!("Verified {}, rejected by server {}, transport failures {} (out of {})",
+             verified, rejected, transport_failed, total);
 }
 
 // Use easy handles in a single thread